@@ -61,14 +61,28 @@ fn main() {
         .with_finish(indicatif::ProgressFinish::AndLeave)
     };
 
-    while let Ok(bytes_read) = generator.read(&mut buf) {
-        if bytes_read == 0 {
-            break;
+    let result = (|| -> std::io::Result<()> {
+        while let Ok(bytes_read) = generator.read(&mut buf) {
+            if bytes_read == 0 {
+                break;
+            }
+            output.write_all(&buf[..bytes_read])?;
+            progress.inc(1);
         }
-        output.write_all(&buf[..bytes_read]).unwrap();
-        progress.inc(1);
-    }
 
-    progress.finish();
-    output.flush().unwrap();
+        output.flush()
+    })();
+
+    match result {
+        Ok(()) => progress.finish(),
+        Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => {
+            let _ = output.flush();
+            progress.finish();
+            std::process::exit(0);
+        }
+        Err(err) => {
+            eprintln!("dicgen: {err}");
+            std::process::exit(1);
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::{error::Error, fmt::Display};
+use core::fmt::{self, Display};
 
 #[derive(Debug)]
 pub enum DictionaryGeneratorError {
@@ -6,11 +6,12 @@ pub enum DictionaryGeneratorError {
 }
 
 impl Display for DictionaryGeneratorError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             DictionaryGeneratorError::AlphabetEmpty => write!(f, "Alphabet is empty, then combinations can't be generated"),
         }
     }
 }
 
-impl Error for DictionaryGeneratorError {}
+#[cfg(feature = "std")]
+impl std::error::Error for DictionaryGeneratorError {}
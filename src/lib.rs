@@ -1,13 +1,26 @@
+#![no_std]
 #![deny(clippy::perf)]
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 mod error;
 
-use std::{collections::BTreeSet, io::Read};
+use alloc::{collections::BTreeSet, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "parallel")]
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::io::Read;
 
 pub use error::DictionaryGeneratorError;
 
 pub struct DictionaryGenerator {
     alphabet: Vec<char>,
+    /// One charset per position (stored least-significant-position-first, like `current_value`),
+    /// set by [`DictionaryGenerator::from_mask`]. `None` means every position shares `alphabet`
+    /// and the word may grow in length by carry, up to `last_value`'s length.
+    masks: Option<Vec<Vec<char>>>,
     last_value: Vec<char>,
     prefix: Option<String>,
     suffix: Option<String>,
@@ -38,6 +51,7 @@ impl DictionaryGenerator {
 
         Ok(DictionaryGenerator {
             alphabet,
+            masks: None,
             last_value,
             prefix: None,
             suffix: None,
@@ -68,6 +82,53 @@ impl DictionaryGenerator {
         DictionaryGenerator::new(alphabet, init.to_string(), end)
     }
 
+    /// Mask mode: `sets[i]` is the charset for position `i`, so every word has the fixed length
+    /// `sets.len()`. Words range from the first char of each set to its last, in order, with no
+    /// growth (carry past the leftmost position ends the iterator instead of lengthening it).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dicgen::DictionaryGenerator;
+    /// let mut generator = DictionaryGenerator::from_mask(&["01", "ab"]).unwrap();
+    ///
+    /// assert_eq!(generator.next(), Some("0a".to_string()));
+    /// assert_eq!(generator.next(), Some("0b".to_string()));
+    /// assert_eq!(generator.next(), Some("1a".to_string()));
+    /// assert_eq!(generator.next(), Some("1b".to_string()));
+    /// assert_eq!(generator.next(), None);
+    /// ```
+    pub fn from_mask<S: AsRef<str>>(sets: &[S]) -> Result<Self, DictionaryGeneratorError> {
+        if sets.is_empty() {
+            return Err(DictionaryGeneratorError::AlphabetEmpty);
+        }
+
+        let mut masks = Vec::with_capacity(sets.len());
+        let mut current_value = Vec::with_capacity(sets.len());
+        let mut last_value = Vec::with_capacity(sets.len());
+        for set in sets.iter().rev() {
+            // Dedupe (order-preserving: a position's enumeration order is caller-defined, unlike
+            // the shared `alphabet`, so it can't be sorted through a `BTreeSet` like `new()` does).
+            let mut seen = BTreeSet::new();
+            let charset: Vec<char> = set.as_ref().chars().filter(|&char| seen.insert(char)).collect();
+            let (Some(&first), Some(&last)) = (charset.first(), charset.last()) else {
+                return Err(DictionaryGeneratorError::AlphabetEmpty);
+            };
+            current_value.push(first);
+            last_value.push(last);
+            masks.push(charset);
+        }
+
+        Ok(DictionaryGenerator {
+            alphabet: Vec::new(),
+            masks: Some(masks),
+            last_value,
+            prefix: None,
+            suffix: None,
+            current_value: Some(current_value),
+        })
+    }
+
     pub fn with_prefix(self, prefix: &str) -> Self {
         if prefix.is_empty() {
             return self;
@@ -95,6 +156,16 @@ impl DictionaryGenerator {
         .unwrap_or(false)
     }
 
+    /// The charset for position `offset` (least-significant-position-first, like
+    /// `current_value`): `masks[offset]` in mask mode, or the shared `alphabet` otherwise.
+    #[inline]
+    fn charset_at(&self, offset: usize) -> &[char] {
+        match &self.masks {
+            Some(masks) => &masks[offset],
+            None => &self.alphabet,
+        }
+    }
+
     #[inline]
     fn update(&mut self) {
         if self.is_last() {
@@ -104,21 +175,34 @@ impl DictionaryGenerator {
             return;
         };
         let mut current_offset = 0;
+        let mut terminated = false;
         loop {
             let offset_value = current_value[current_offset];
-            if let Some(next_value) = self.alphabet.iter().skip_while(|&value| value != &offset_value).nth(1) {
+            let charset = match &self.masks {
+                Some(masks) => &masks[current_offset],
+                None => &self.alphabet,
+            };
+            if let Some(next_value) = charset.iter().skip_while(|&value| value != &offset_value).nth(1) {
                 current_value[current_offset] = *next_value;
                 break;
             }
             // Carriage.
-            let first_letter = self.alphabet[0];
+            let first_letter = charset[0];
             current_value[current_offset] = first_letter;
             if current_offset == current_value.len() - 1 {
-                current_value.push(first_letter);
+                if self.masks.is_some() {
+                    // Mask mode is fixed-length: carry past the leftmost position ends the sequence.
+                    terminated = true;
+                } else {
+                    current_value.push(first_letter);
+                }
                 break;
             }
             current_offset += 1;
         }
+        if terminated {
+            self.current_value = None;
+        }
     }
 
     #[inline]
@@ -152,14 +236,283 @@ impl DictionaryGenerator {
         Some(())
     }
 
+    /// In mask mode `init` is cut down to [`DictionaryGenerator::from_mask`]'s fixed word
+    /// length if it's longer, keeping its trailing chars and dropping the leading ones, since every other position lookup in mask mode indexes `masks` by
+    /// offset into `current_value` and a longer `init` would otherwise run that index out of
+    /// bounds.
     pub fn reset_starting_in(&mut self, init: &str) {
         let mut current_value = self.current_value.take().unwrap();
         current_value.clear();
         current_value.extend(init.chars().rev());
+        if let Some(masks) = &self.masks {
+            current_value.truncate(masks.len());
+        }
         self.current_value = Some(current_value);
     }
+
+    /// Index of `char` within the charset for position `offset`.
+    #[inline]
+    fn digit_of(&self, offset: usize, char: char) -> usize {
+        match &self.masks {
+            Some(masks) => masks[offset].iter().position(|&candidate| candidate == char).unwrap_or(0),
+            None => self.alphabet.binary_search(&char).unwrap_or(0),
+        }
+    }
+
+    /// Rank of `value` (stored least-significant-position-first) as a mixed-radix number, each
+    /// position's base being the size of [`DictionaryGenerator::charset_at`] for that position
+    /// (uniformly `alphabet.len()` outside mask mode).
+    #[inline]
+    fn rank(&self, value: &[char]) -> u128 {
+        let mut rank: u128 = 0;
+        let mut place: u128 = 1;
+        for (offset, &char) in value.iter().enumerate() {
+            let digit = self.digit_of(offset, char) as u128;
+            rank = rank.saturating_add(digit.saturating_mul(place));
+            place = place.saturating_mul(self.charset_at(offset).len() as u128);
+        }
+        rank
+    }
+
+    /// Inverse of [`DictionaryGenerator::rank`]: the `length` digits (least-significant-first)
+    /// of `index` in the same mixed radix.
+    #[inline]
+    fn unrank(&self, mut index: u128, length: usize) -> Vec<char> {
+        let mut digits = Vec::with_capacity(length);
+        for offset in 0..length {
+            let charset = self.charset_at(offset);
+            let base = charset.len() as u128;
+            let digit = (index % base) as usize;
+            digits.push(charset[digit]);
+            index /= base;
+        }
+        digits
+    }
+
+    /// Resolves `offset` positions ahead of `current_value` into an exact `(rank, length)`,
+    /// walking lengths upward (full `base.pow(length)` ranges until the capped final length).
+    /// Returns `None` once `offset` runs past `last_value`.
+    fn locate(&self, offset: u128) -> Option<(u128, usize)> {
+        let current_value = self.current_value.as_ref()?;
+        let base = self.alphabet.len() as u128;
+        let mut index = self.rank(current_value).saturating_add(offset);
+        let mut length = current_value.len();
+        loop {
+            if length == self.last_value.len() {
+                let last_rank = self.rank(&self.last_value);
+                return if index > last_rank { None } else { Some((index, length)) };
+            }
+            let capacity = base.saturating_pow(length as u32);
+            if index < capacity {
+                return Some((index, length));
+            }
+            index -= capacity;
+            length += 1;
+        }
+    }
+
+    fn word_at(&self, rank: u128, length: usize) -> String {
+        let digits = self.unrank(rank, length);
+        let mut word = String::with_capacity(
+            length
+            + self.prefix.as_deref().map_or(0, str::len)
+            + self.suffix.as_deref().map_or(0, str::len)
+        );
+        if let Some(prefix) = &self.prefix {
+            word.push_str(prefix);
+        }
+        for &char in digits.iter().rev() {
+            word.push(char);
+        }
+        if let Some(suffix) = &self.suffix {
+            word.push_str(suffix);
+        }
+        word
+    }
+
+    /// Exact count of words this generator will still emit, including the current one.
+    ///
+    /// Unlike [`Iterator::size_hint`], which is capped at `usize::MAX`, this is a `u128` that
+    /// saturates rather than wraps, so it stays exact for realistic alphabets/lengths: it counts
+    /// directly from `current_value` to `last_value` instead of iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dicgen::DictionaryGenerator;
+    /// let generator = DictionaryGenerator::new("abc", "b", "ab").unwrap();
+    ///
+    /// assert_eq!(generator.total(), 4);
+    /// ```
+    pub fn total(&self) -> u128 {
+        let Some(current_value) = self.current_value.as_ref() else {
+            return 0;
+        };
+        let base = self.alphabet.len() as u128;
+        let current_len = current_value.len();
+        let last_len = self.last_value.len();
+        if current_len > last_len {
+            return 0;
+        }
+        if current_len == last_len {
+            return self.rank(&self.last_value).saturating_sub(self.rank(current_value)).saturating_add(1);
+        }
+
+        let mut total = base.saturating_pow(current_len as u32).saturating_sub(self.rank(current_value));
+        for length in (current_len + 1)..last_len {
+            total = total.saturating_add(base.saturating_pow(length as u32));
+        }
+        total.saturating_add(self.rank(&self.last_value).saturating_add(1))
+    }
+
+    /// The word `index` positions ahead of the current one, computed by direct unranking
+    /// instead of iterating `index` times. `nth_word(0)` is equivalent to peeking at the next
+    /// value [`Iterator::next`] would yield.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dicgen::DictionaryGenerator;
+    /// let generator = DictionaryGenerator::new("abc", "b", "ab").unwrap();
+    ///
+    /// assert_eq!(generator.nth_word(0), Some("b".to_string()));
+    /// assert_eq!(generator.nth_word(2), Some("aa".to_string()));
+    /// assert_eq!(generator.nth_word(3), Some("ab".to_string()));
+    /// assert_eq!(generator.nth_word(4), None);
+    /// ```
+    pub fn nth_word(&self, index: u128) -> Option<String> {
+        let (rank, length) = self.locate(index)?;
+        Some(self.word_at(rank, length))
+    }
+
+    /// Advances `current_value` by `n` positions using the same direct unranking as
+    /// [`DictionaryGenerator::nth_word`], instead of calling [`DictionaryGenerator::update`]
+    /// (which [`Iterator::next`] calls once per word) `n` times.
+    ///
+    /// Named `skip_ahead` rather than `skip` so it isn't shadowed by [`Iterator::skip`], which
+    /// this type already inherits and which takes `self` by value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dicgen::DictionaryGenerator;
+    /// let mut generator = DictionaryGenerator::new("abc", "b", "ab").unwrap();
+    /// generator.skip_ahead(2);
+    ///
+    /// assert_eq!(generator.next(), Some("aa".to_string()));
+    /// ```
+    pub fn skip_ahead(&mut self, n: u128) {
+        if n == 0 {
+            return;
+        }
+        match self.locate(n) {
+            Some((rank, length)) => self.current_value = Some(self.unrank(rank, length)),
+            None => self.current_value = None,
+        }
+    }
+
+    /// A fresh generator covering only the half-open `range` of offsets ahead of the current
+    /// position, seeded via direct unranking. `range` must not be empty nor run past
+    /// [`DictionaryGenerator::total`].
+    #[cfg(all(feature = "parallel", feature = "std"))]
+    fn sub_generator(&self, range: Range<u128>) -> Option<Self> {
+        if range.start >= range.end {
+            return None;
+        }
+        let (start_rank, start_len) = self.locate(range.start)?;
+        let (end_rank, end_len) = self.locate(range.end - 1)?;
+        Some(DictionaryGenerator {
+            alphabet: self.alphabet.clone(),
+            masks: self.masks.clone(),
+            last_value: self.unrank(end_rank, end_len),
+            prefix: self.prefix.clone(),
+            suffix: self.suffix.clone(),
+            current_value: Some(self.unrank(start_rank, start_len)),
+        })
+    }
+
+    /// Writes the remaining keyspace to `out` using `threads` workers, each generating a
+    /// contiguous, non-overlapping range of [`DictionaryGenerator::total`] computed up front and
+    /// seeded via direct unranking. Concatenating the workers' output in range order reproduces
+    /// exactly what the sequential `Iterator`/`Read` path would have written.
+    ///
+    /// Each worker streams its words to a bounded channel in [`WRITE_PARALLEL_BATCH_WORDS`]-sized
+    /// batches instead of materializing its whole range; a single writer thread drains the
+    /// channels in range order, so peak memory stays near
+    /// `threads * (WRITE_PARALLEL_CHANNEL_CAPACITY + 1)` batches rather than
+    /// `O(total output size)`, and output starts reaching `out` well before the last worker
+    /// finishes.
+    #[cfg(all(feature = "parallel", feature = "std"))]
+    pub fn write_parallel<W: std::io::Write>(&self, mut out: W, threads: usize) -> std::io::Result<u64> {
+        let total = self.total();
+        if total == 0 {
+            return Ok(0);
+        }
+        let threads = (threads.max(1) as u128).min(total) as usize;
+
+        let chunk = total / threads as u128;
+        let extra = total % threads as u128;
+        let mut ranges = Vec::with_capacity(threads);
+        let mut start = 0u128;
+        for worker in 0..threads {
+            let len = chunk + if (worker as u128) < extra { 1 } else { 0 };
+            ranges.push(start..start + len);
+            start += len;
+        }
+
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = ranges.into_iter().map(|range| {
+                let (sender, receiver) = std::sync::mpsc::sync_channel::<Vec<u8>>(WRITE_PARALLEL_CHANNEL_CAPACITY);
+                let handle = scope.spawn(move || {
+                    let Some(sub_generator) = self.sub_generator(range) else {
+                        return;
+                    };
+                    let mut batch = Vec::new();
+                    let mut batched_words = 0;
+                    for word in sub_generator {
+                        batch.extend_from_slice(word.as_bytes());
+                        batch.push(b'\n');
+                        batched_words += 1;
+                        if batched_words >= WRITE_PARALLEL_BATCH_WORDS {
+                            if sender.send(core::mem::take(&mut batch)).is_err() {
+                                return;
+                            }
+                            batched_words = 0;
+                        }
+                    }
+                    if !batch.is_empty() {
+                        let _ = sender.send(batch);
+                    }
+                });
+                (handle, receiver)
+            }).collect();
+
+            let mut written = 0u64;
+            for (handle, receiver) in workers {
+                for batch in receiver {
+                    out.write_all(&batch)?;
+                    written += batch.len() as u64;
+                }
+                // A panicking worker drops its sender during unwind, which ends the `for batch in
+                // receiver` loop exactly like a normal finish would. Without this join, that
+                // truncated range would read as a success instead of the error it is.
+                if handle.join().is_err() {
+                    return Err(std::io::Error::new(std::io::ErrorKind::Other, "a dicgen worker thread panicked"));
+                }
+            }
+            out.flush()?;
+            Ok(written)
+        })
+    }
 }
 
+/// Words per batch sent down a [`DictionaryGenerator::write_parallel`] worker's channel.
+#[cfg(all(feature = "parallel", feature = "std"))]
+const WRITE_PARALLEL_BATCH_WORDS: usize = 1024;
+/// Batches a [`DictionaryGenerator::write_parallel`] worker may queue before blocking.
+#[cfg(all(feature = "parallel", feature = "std"))]
+const WRITE_PARALLEL_CHANNEL_CAPACITY: usize = 4;
+
 impl Iterator for DictionaryGenerator {
     type Item = String;
 
@@ -171,23 +524,14 @@ impl Iterator for DictionaryGenerator {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let current_value_len = self.current_value.as_ref().map(|value| value.len()).unwrap_or(self.last_value.len());
-
-        let mut max_possible_values = 0;
-        for i in current_value_len..=self.last_value.len() {
-            max_possible_values += self.alphabet.len().pow(i as u32);
-        }
-
-        let min_possible_values = if self.current_value.is_none() {
-            0
-        } else {
-            1
-        };
+        let total = self.total().min(usize::MAX as u128) as usize;
+        let min_possible_values = if self.current_value.is_none() { 0 } else { total.min(1) };
 
-        (min_possible_values, Some(max_possible_values))
+        (min_possible_values, Some(total))
     }
 }
 
+#[cfg(feature = "std")]
 impl Read for DictionaryGenerator {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         let Some(current_chars) = self.current_value.as_ref() else {
@@ -226,5 +570,77 @@ impl Read for DictionaryGenerator {
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[test]
+    fn total_and_nth_word_at_first_and_last_index() {
+        let generator = DictionaryGenerator::new("abc", "b", "ab").unwrap();
+
+        assert_eq!(generator.total(), 4);
+        assert_eq!(generator.nth_word(0), Some("b".to_string()));
+        assert_eq!(generator.nth_word(3), Some("ab".to_string()));
+        assert_eq!(generator.nth_word(4), None);
+    }
+
+    #[test]
+    fn skip_ahead_past_the_end_exhausts_the_generator() {
+        let mut generator = DictionaryGenerator::new("abc", "b", "ab").unwrap();
+        generator.skip_ahead(100);
+
+        assert_eq!(generator.next(), None);
+        assert_eq!(generator.total(), 0);
+    }
+
+    #[test]
+    fn from_mask_dedupes_a_charset_with_repeated_chars() {
+        let mut generator = DictionaryGenerator::from_mask(&["aab"]).unwrap();
 
+        assert_eq!(generator.next(), Some("a".to_string()));
+        assert_eq!(generator.next(), Some("b".to_string()));
+        assert_eq!(generator.next(), None);
+    }
+
+    #[test]
+    fn from_mask_rejects_an_empty_charset() {
+        assert!(matches!(
+            DictionaryGenerator::from_mask(&["ab", ""]),
+            Err(DictionaryGeneratorError::AlphabetEmpty)
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "parallel", feature = "std"))]
+    fn write_parallel_with_more_threads_than_words_still_covers_every_word() {
+        let generator = DictionaryGenerator::new("abc", "b", "ab").unwrap();
+
+        let mut out = Vec::new();
+        let written = generator.write_parallel(&mut out, 100).unwrap();
+
+        assert_eq!(written, out.len() as u64);
+        assert_eq!(out, b"b\nc\naa\nab\n");
+    }
+
+    #[test]
+    #[cfg(all(feature = "parallel", feature = "std"))]
+    fn write_parallel_streams_a_single_range_spanning_multiple_batches() {
+        let generator = DictionaryGenerator::new("01", "0", "111111111111").unwrap();
+        let total = generator.total();
+        assert!(total > WRITE_PARALLEL_BATCH_WORDS as u128, "test needs a range spanning more than one batch");
+
+        let mut out = Vec::new();
+        let written = generator.write_parallel(&mut out, 1).unwrap();
+
+        assert_eq!(written, out.len() as u64);
+        assert_eq!(out.iter().filter(|&&byte| byte == b'\n').count() as u128, total);
+        assert!(out.starts_with(b"0\n"));
+        assert!(out.ends_with(b"111111111111\n"));
+    }
+
+    #[test]
+    fn reset_starting_in_truncates_an_overlong_string_in_mask_mode() {
+        let mut generator = DictionaryGenerator::from_mask(&["01", "ab"]).unwrap();
+        generator.reset_starting_in("0ab");
+
+        assert_eq!(generator.next(), Some("ab".to_string()));
+    }
 }
@@ -1,9 +1,13 @@
 use dicgen::DictionaryGenerator;
 
 use clap::Parser;
-use indicatif::ProgressIterator;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::io::{Write, BufWriter};
+use std::time::{Duration, Instant};
+
+/// How often `--checkpoint` is refreshed, whichever comes first.
+const CHECKPOINT_WORDS_INTERVAL: u64 = 10_000;
+const CHECKPOINT_TIME_INTERVAL: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Parser)]
 struct Opts {
@@ -19,6 +23,28 @@ struct Opts {
     /// Hide progress bar when writing to file (writing to stdout always hide it).
     #[clap(short, long)]
     without_progress_bar: bool,
+    /// Periodically write the next pending word here, so a killed run can be resumed.
+    #[clap(long)]
+    checkpoint: Option<PathBuf>,
+    /// Resume from the word stored in `--checkpoint` instead of `--init`.
+    #[clap(long, requires = "checkpoint")]
+    resume: bool,
+    /// Generate using this many worker threads instead of the sequential path. Incompatible with
+    /// `--checkpoint`/`--resume`, since parallel workers don't advance a single resumable cursor.
+    #[cfg(feature = "parallel")]
+    #[clap(short, long, conflicts_with_all = ["checkpoint", "resume"])]
+    threads: Option<usize>,
+}
+
+/// Writes `word` to `path` via a temp file + rename, so a process killed mid-write can't leave
+/// `path` holding a truncated or partial checkpoint.
+fn write_checkpoint(path: &Path, word: &str) -> std::io::Result<()> {
+    let mut temp_path = path.as_os_str().to_os_string();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    std::fs::write(&temp_path, word)?;
+    std::fs::rename(&temp_path, path)
 }
 
 fn main() {
@@ -30,11 +56,37 @@ fn main() {
         BufWriter::new(Box::new(std::io::stdout().lock()))
     };
 
-    let generator = if let Some(init) = opts.init {
+    let mut generator = if let Some(init) = opts.init {
         DictionaryGenerator::new(opts.alphabet, init, opts.end)
     } else {
         DictionaryGenerator::new_from_start(opts.alphabet, opts.end)
-    };
+    }
+    .unwrap();
+
+    if opts.resume {
+        let checkpoint = opts.checkpoint.as_ref().expect("--resume requires --checkpoint");
+        let resume_word = match std::fs::read_to_string(checkpoint) {
+            Ok(resume_word) => resume_word,
+            Err(err) => {
+                eprintln!("dicgen: failed to read checkpoint file: {err}");
+                std::process::exit(1);
+            }
+        };
+        generator.reset_starting_in(resume_word.trim());
+    }
+
+    #[cfg(feature = "parallel")]
+    if let Some(threads) = opts.threads {
+        let result = generator.write_parallel(&mut output, threads);
+        match result {
+            Ok(_) => return,
+            Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => return,
+            Err(err) => {
+                eprintln!("dicgen: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
 
     let progress = if opts.file.is_none() || opts.without_progress_bar {
         indicatif::ProgressBar::hidden()
@@ -49,10 +101,43 @@ fn main() {
         .with_finish(indicatif::ProgressFinish::AndLeave)
     };
 
-    for value in generator.progress_with(progress) {
-        output.write_all(value.as_bytes()).unwrap();
-        output.write_all("\n".as_bytes()).unwrap();
-    }
+    let mut words_since_checkpoint = 0u64;
+    let mut last_checkpoint_at = Instant::now();
+
+    let result = (|| -> std::io::Result<()> {
+        while let Some(value) = generator.next() {
+            output.write_all(value.as_bytes())?;
+            output.write_all("\n".as_bytes())?;
+            progress.inc(1);
 
-    output.flush().unwrap();
+            if let Some(checkpoint) = &opts.checkpoint {
+                words_since_checkpoint += 1;
+                if words_since_checkpoint >= CHECKPOINT_WORDS_INTERVAL || last_checkpoint_at.elapsed() >= CHECKPOINT_TIME_INTERVAL {
+                    if let Some(next_word) = generator.nth_word(0) {
+                        // Flush first: the checkpoint must never point ahead of what's durably
+                        // written, or a kill between these two lines loses the buffered words.
+                        output.flush()?;
+                        write_checkpoint(checkpoint, &next_word)?;
+                    }
+                    words_since_checkpoint = 0;
+                    last_checkpoint_at = Instant::now();
+                }
+            }
+        }
+
+        output.flush()
+    })();
+
+    match result {
+        Ok(()) => progress.finish(),
+        Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => {
+            let _ = output.flush();
+            progress.finish();
+            std::process::exit(0);
+        }
+        Err(err) => {
+            eprintln!("dicgen: {err}");
+            std::process::exit(1);
+        }
+    }
 }